@@ -0,0 +1,43 @@
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_sdk::json_types::U128;
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::Contract;
+
+#[near_bindgen]
+impl Contract {
+    /// Blocks `account_id` from sending or receiving tokens. Restricted to the contract owner.
+    pub fn blacklist_account(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.blacklist.insert(&account_id);
+    }
+
+    /// Lifts a previously imposed block on `account_id`. Restricted to the contract owner.
+    pub fn unblacklist_account(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.blacklist.remove(&account_id);
+    }
+
+    /// Returns whether `account_id` is currently blocked from transferring or receiving tokens.
+    pub fn is_blacklisted(&self, account_id: AccountId) -> bool {
+        self.blacklist.contains(&account_id)
+    }
+
+    /// Moves a blacklisted account's entire balance to the contract owner. Restricted to the
+    /// owner; `account_id` must already be blacklisted.
+    pub fn seize(&mut self, account_id: AccountId) -> U128 {
+        self.assert_owner();
+        assert!(self.blacklist.contains(&account_id), "Account is not blacklisted");
+        let balance = self.token.ft_balance_of(account_id.clone()).0;
+        if balance > 0 {
+            let owner_id = self.owner_id.clone();
+            self.token.internal_withdraw(&account_id, balance);
+            self.token.internal_deposit(&owner_id, balance);
+        }
+        balance.into()
+    }
+
+    pub(crate) fn assert_not_blacklisted(&self, account_id: &AccountId) {
+        assert!(!self.blacklist.contains(account_id), "Account is blacklisted");
+    }
+}
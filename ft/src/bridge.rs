@@ -0,0 +1,92 @@
+use near_contract_standards::fungible_token::events::FtMint;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::Contract;
+
+/// Encoding a relayer should expect when parsing the log `withdraw` emits, so both Borsh- and
+/// JSON-based relayers can be supported without changing the contract.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum WithdrawSerializeType {
+    Borsh,
+    Json,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct WithdrawEvent {
+    recipient_eth_address: [u8; 20],
+    amount: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sets the account allowed to call `deposit`. Restricted to the contract owner.
+    pub fn set_bridge_account(&mut self, bridge_account: AccountId) {
+        self.assert_owner();
+        self.bridge_account = bridge_account;
+    }
+
+    /// Returns the account currently allowed to call `deposit`.
+    pub fn bridge_account(&self) -> AccountId {
+        self.bridge_account.clone()
+    }
+
+    /// Mints `amount` to `receiver_id` on behalf of a lock that already happened on the
+    /// Ethereum side. Restricted to `bridge_account`, the relayer that verified that lock's
+    /// proof off-chain before submitting this call.
+    pub fn deposit(&mut self, receiver_id: AccountId, amount: U128) {
+        self.assert_not_paused();
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.bridge_account,
+            "Only the bridge account can call deposit"
+        );
+        self.assert_not_blacklisted(&receiver_id);
+        self.token.internal_deposit(&receiver_id, amount.0);
+        FtMint {
+            owner_id: &receiver_id,
+            amount: &amount,
+            memo: Some("Bridged deposit from Ethereum"),
+        }
+        .emit();
+    }
+
+    /// Burns `amount` from the caller's balance and emits a structured log carrying the
+    /// Ethereum recipient address, for a relayer to pick up and release on the Ethereum side.
+    pub fn withdraw(&mut self, recipient_eth_address: [u8; 20], amount: U128) {
+        self.assert_not_paused();
+        let sender_id = env::predecessor_account_id();
+        self.assert_not_blacklisted(&sender_id);
+        self.token.internal_withdraw(&sender_id, amount.0);
+
+        let event = WithdrawEvent { recipient_eth_address, amount };
+        let payload = match self.withdraw_serialize_type {
+            WithdrawSerializeType::Borsh => hex_encode(&event.try_to_vec().unwrap()),
+            WithdrawSerializeType::Json => near_sdk::serde_json::to_string(&event).unwrap(),
+        };
+        env::log_str(&format!(
+            "EVENT_JSON:{{\"standard\":\"nep141-eth-bridge\",\"event\":\"withdraw\",\"data\":{}}}",
+            payload
+        ));
+    }
+}
+
+pub(crate) fn default_withdraw_serialize_type() -> WithdrawSerializeType {
+    WithdrawSerializeType::Borsh
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2 + 2);
+    out.push('"');
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,106 @@
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::events::FtTransfer;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, AccountId, Balance, PromiseOrValue};
+
+use crate::Contract;
+
+/// Share of the collected fee that goes to the referrer instead of the treasury, when a
+/// referrer is supplied. Expressed in basis points of the fee itself (not of the transfer).
+const REFERRAL_SHARE_OF_FEE_BASIS_POINTS: u16 = 5_000; // 50% of the fee
+
+#[near_bindgen]
+impl Contract {
+    /// Sets the per-transfer fee (in basis points of the transferred amount) and the account
+    /// that collects it. Restricted to the contract owner.
+    pub fn set_fee_config(&mut self, fee_basis_points: u16, fee_collector: AccountId) {
+        self.assert_owner();
+        assert!(fee_basis_points < 10_000, "fee_basis_points must be less than 10000");
+        self.fee_basis_points = fee_basis_points;
+        self.fee_collector = fee_collector;
+    }
+
+    /// Current per-transfer fee, in basis points of the transferred amount.
+    pub fn fee_basis_points(&self) -> u16 {
+        self.fee_basis_points
+    }
+
+    /// Total amount a `referrer` has earned from referred transfers so far.
+    pub fn referral_earnings_of(&self, referrer: AccountId) -> U128 {
+        self.referral_earnings.get(&referrer).unwrap_or(0).into()
+    }
+
+    /// Same as `ft_transfer_call`, but splits part of the transfer fee to `referrer` instead of
+    /// sending it all to the fee collector.
+    #[payable]
+    pub fn ft_transfer_call_with_referral(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+        referrer: Option<AccountId>,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_paused();
+        let sender_id = env::predecessor_account_id();
+        self.assert_not_blacklisted(&sender_id);
+        self.assert_not_blacklisted(&receiver_id);
+        let net_amount = self.internal_collect_fee(&sender_id, amount.0, referrer.as_ref());
+        self.token.ft_transfer_call(receiver_id, net_amount.into(), memo, msg)
+    }
+
+    /// Withdraws the configured fee out of `sender_id`'s balance and routes it to the fee
+    /// collector (optionally splitting part of it to `referrer`), returning the amount that
+    /// should still be delivered to the receiver via `token.ft_transfer`/`ft_transfer_call`.
+    pub(crate) fn internal_collect_fee(
+        &mut self,
+        sender_id: &AccountId,
+        amount: Balance,
+        referrer: Option<&AccountId>,
+    ) -> Balance {
+        let fee = amount
+            .checked_mul(self.fee_basis_points as u128)
+            .unwrap_or_else(|| env::panic_str("Fee calculation overflow"))
+            / 10_000;
+        if fee == 0 {
+            return amount;
+        }
+        self.token.internal_withdraw(sender_id, fee);
+
+        let referral_share = match referrer {
+            Some(referrer) if referrer != sender_id => {
+                (fee * REFERRAL_SHARE_OF_FEE_BASIS_POINTS as u128) / 10_000
+            }
+            _ => 0,
+        };
+        let treasury_share = fee - referral_share;
+
+        if treasury_share > 0 {
+            let fee_collector = self.fee_collector.clone();
+            self.assert_not_blacklisted(&fee_collector);
+            self.token.internal_deposit(&fee_collector, treasury_share);
+            FtTransfer {
+                old_owner_id: sender_id,
+                new_owner_id: &fee_collector,
+                amount: &treasury_share.into(),
+                memo: Some("transfer fee"),
+            }
+            .emit();
+        }
+        if referral_share > 0 {
+            let referrer = referrer.unwrap().clone();
+            self.assert_not_blacklisted(&referrer);
+            self.token.internal_deposit(&referrer, referral_share);
+            let earned = self.referral_earnings.get(&referrer).unwrap_or(0) + referral_share;
+            self.referral_earnings.insert(&referrer, &earned);
+            FtTransfer {
+                old_owner_id: sender_id,
+                new_owner_id: &referrer,
+                amount: &referral_share.into(),
+                memo: Some("referral share"),
+            }
+            .emit();
+        }
+        amount - fee
+    }
+}
@@ -0,0 +1,52 @@
+use near_contract_standards::fungible_token::events::FtMint;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::Contract;
+
+#[near_bindgen]
+impl Contract {
+    /// Mints `amount` new tokens to `receiver_id`. Restricted to accounts added via
+    /// `add_minter`. Panics if minting would push the total supply past `max_supply`
+    /// (when one is configured).
+    pub fn ft_mint(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        let minter_id = env::predecessor_account_id();
+        assert!(self.minters.contains(&minter_id), "Only a minter can call ft_mint");
+        self.assert_not_blacklisted(&receiver_id);
+        if let Some(max_supply) = self.max_supply {
+            let new_supply = self
+                .token
+                .total_supply
+                .checked_add(amount.0)
+                .unwrap_or_else(|| env::panic_str("Total supply overflow"));
+            assert!(new_supply <= max_supply.0, "Minting would exceed max_supply");
+        }
+        self.token.internal_deposit(&receiver_id, amount.0);
+        FtMint { owner_id: &receiver_id, amount: &amount, memo: memo.as_deref() }.emit();
+    }
+
+    /// Grants `account_id` permission to call `ft_mint`. Restricted to the contract owner.
+    pub fn add_minter(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.minters.insert(&account_id);
+    }
+
+    /// Revokes `account_id`'s permission to call `ft_mint`. Restricted to the contract owner.
+    pub fn remove_minter(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.minters.remove(&account_id);
+    }
+
+    /// Returns whether `account_id` is currently allowed to call `ft_mint`.
+    pub fn is_minter(&self, account_id: AccountId) -> bool {
+        self.minters.contains(&account_id)
+    }
+
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::Contract;
+
+#[near_bindgen]
+impl Contract {
+    /// Halts `ft_transfer`/`ft_transfer_call`. Restricted to accounts added via `add_pauser`.
+    pub fn pause(&mut self) {
+        self.assert_pauser();
+        self.paused = true;
+    }
+
+    /// Resumes transfers after a `pause`. Restricted to accounts added via `add_pauser`.
+    pub fn unpause(&mut self) {
+        self.assert_pauser();
+        self.paused = false;
+    }
+
+    /// Returns whether transfers are currently halted.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Grants `account_id` permission to call `pause`/`unpause`. Restricted to the contract owner.
+    pub fn add_pauser(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.pausers.insert(&account_id);
+    }
+
+    /// Revokes `account_id`'s permission to call `pause`/`unpause`. Restricted to the contract owner.
+    pub fn remove_pauser(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.pausers.remove(&account_id);
+    }
+
+    pub(crate) fn assert_not_paused(&self) {
+        assert!(!self.paused, "Transfers are paused");
+    }
+
+    fn assert_pauser(&self) {
+        assert!(
+            self.pausers.contains(&env::predecessor_account_id()),
+            "Only a pauser can call this method"
+        );
+    }
+}
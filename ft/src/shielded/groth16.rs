@@ -0,0 +1,140 @@
+//! Groth16 proof verification over the alt_bn128 curve, using the pairing precompiles NEAR
+//! exposes through `env::alt_bn128_*`.
+//!
+//! There is no hardcoded verifying key in this file. A Groth16 VK is only meaningful once it's
+//! the output of a real trusted setup over this pool's circuit, and that ceremony hasn't
+//! happened yet in this tree, so embedding any fixed bytes here (zero or otherwise) would either
+//! be inert or, worse, silently accept forged proofs. Instead the VK is owner-configured contract
+//! state (see `Contract::set_shielded_verifying_key` in `mod.rs`), defaulting to unset; `verify`
+//! requires one to be passed in, and `mod.rs` refuses to reach it until one has been set, so
+//! `unshield`/`private_transfer` stay unreachable until a real ceremony output is deployed.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::env;
+use near_sdk::serde::{Deserialize, Serialize};
+
+const G1_LEN: usize = 64;
+const G2_LEN: usize = 128;
+
+/// Groth16 proof: `a`, `c` are G1 points, `b` is a G2 point, each in uncompressed alt_bn128
+/// encoding (little-endian coordinate pairs, matching `env::alt_bn128_*`'s expected format).
+/// Passed in as a method argument, so it's (de)serialized via JSON rather than Borsh.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proof {
+    pub a: [u8; G1_LEN],
+    pub b: [u8; G2_LEN],
+    pub c: [u8; G1_LEN],
+}
+
+/// `[known_root, nullifier_hash, recipient, amount]`.
+pub const PUBLIC_INPUTS: usize = 4;
+
+/// Verifying key for this pool's circuit, in the same uncompressed alt_bn128 point encoding as
+/// `Proof`. Points are stored as `Vec<u8>` (rather than fixed-size arrays) so the type round-trips
+/// through both Borsh (contract state) and JSON (the owner-facing setter) without a custom
+/// serializer; `assert_well_formed` checks lengths before a key is ever stored.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VerifyingKey {
+    pub alpha_g1: Vec<u8>,
+    pub beta_g2: Vec<u8>,
+    pub gamma_g2: Vec<u8>,
+    pub delta_g2: Vec<u8>,
+    // One base point per public input, plus a leading constant term (`gamma_abc[0]`).
+    pub gamma_abc_g1: Vec<Vec<u8>>,
+}
+
+impl VerifyingKey {
+    /// Checks that every point has the length `alt_bn128_*` expects, so a malformed key fails
+    /// loudly when the owner sets it instead of panicking deep inside a pairing check the first
+    /// time someone tries to spend a note.
+    pub fn assert_well_formed(&self) {
+        assert_eq!(self.alpha_g1.len(), G1_LEN, "alpha_g1 must be {} bytes", G1_LEN);
+        assert_eq!(self.beta_g2.len(), G2_LEN, "beta_g2 must be {} bytes", G2_LEN);
+        assert_eq!(self.gamma_g2.len(), G2_LEN, "gamma_g2 must be {} bytes", G2_LEN);
+        assert_eq!(self.delta_g2.len(), G2_LEN, "delta_g2 must be {} bytes", G2_LEN);
+        assert_eq!(
+            self.gamma_abc_g1.len(),
+            PUBLIC_INPUTS + 1,
+            "gamma_abc_g1 must have {} entries",
+            PUBLIC_INPUTS + 1
+        );
+        for point in &self.gamma_abc_g1 {
+            assert_eq!(point.len(), G1_LEN, "gamma_abc_g1 entries must be {} bytes", G1_LEN);
+        }
+    }
+}
+
+fn field_element(value: &[u8; 32]) -> [u8; 32] {
+    let mut le = *value;
+    le.reverse();
+    le
+}
+
+/// Computes `gamma_abc[0] + sum(input_i * gamma_abc[i + 1])` via the `alt_bn128_g1_multiexp`
+/// precompile, then verifies the Groth16 pairing equation
+/// `e(A, B) = e(alpha, beta) * e(vk_x, gamma) * e(C, delta)` via `alt_bn128_pairing_check`.
+///
+/// Rejects `proof.a`/`proof.c` at the G1 identity point outright: a legitimate Groth16 proof
+/// never places either there, but the pairing equation degenerates in a way that's trivial to
+/// satisfy with the identity, so this is checked before spending gas on the precompiles.
+pub fn verify(vk: &VerifyingKey, proof: &Proof, public_inputs: &[[u8; 32]; PUBLIC_INPUTS]) -> bool {
+    if is_identity(&proof.a) || is_identity(&proof.c) {
+        return false;
+    }
+
+    let mut multiexp_input = Vec::with_capacity((G1_LEN + 32) * (PUBLIC_INPUTS + 1));
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    multiexp_input.extend_from_slice(&vk.gamma_abc_g1[0]);
+    multiexp_input.extend_from_slice(&one); // constant term's scalar is always 1
+    for (i, input) in public_inputs.iter().enumerate() {
+        multiexp_input.extend_from_slice(&vk.gamma_abc_g1[i + 1]);
+        multiexp_input.extend_from_slice(&field_element(input));
+    }
+    let vk_x = env::alt_bn128_g1_multiexp(&multiexp_input);
+
+    let mut pairing_input = Vec::with_capacity(G1_LEN * 4 + G2_LEN * 4);
+    pairing_input.extend_from_slice(&negate_g1(&proof.a));
+    pairing_input.extend_from_slice(&proof.b);
+    pairing_input.extend_from_slice(&vk.alpha_g1);
+    pairing_input.extend_from_slice(&vk.beta_g2);
+    pairing_input.extend_from_slice(&vk_x);
+    pairing_input.extend_from_slice(&vk.gamma_g2);
+    pairing_input.extend_from_slice(&proof.c);
+    pairing_input.extend_from_slice(&vk.delta_g2);
+    env::alt_bn128_pairing_check(&pairing_input)
+}
+
+fn is_identity(point: &[u8; G1_LEN]) -> bool {
+    point.iter().all(|b| *b == 0)
+}
+
+/// Negates a G1 point's `y` coordinate mod the alt_bn128 base field, so the pairing check can
+/// fold `e(A, B)` into the product on the right-hand side as `e(-A, B) * (...) == 1`.
+fn negate_g1(point: &[u8; G1_LEN]) -> [u8; G1_LEN] {
+    const FIELD_MODULUS: [u8; 32] = [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+        0x5d, 0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c,
+        0xfd, 0x47,
+    ];
+    let mut out = *point;
+    let y = &point[32..64];
+    if y.iter().all(|b| *b == 0) {
+        return out;
+    }
+    // `y` is little-endian (see `Proof`), while `FIELD_MODULUS` above is written big-endian.
+    let mut borrow = 0i16;
+    for i in 0..32 {
+        let diff = FIELD_MODULUS[31 - i] as i16 - y[i] as i16 - borrow;
+        if diff < 0 {
+            out[32 + i] = (diff + 256) as u8;
+            borrow = 1;
+        } else {
+            out[32 + i] = diff as u8;
+            borrow = 0;
+        }
+    }
+    out
+}
@@ -0,0 +1,101 @@
+//! Append-only Merkle tree of commitment leaves, hashed with [`super::mimc`]. Only the path from
+//! each newly inserted leaf up to the root is recomputed; empty subtrees are represented lazily
+//! via precomputed "zero hashes" instead of being stored.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, Vector};
+
+use super::mimc;
+
+/// Depth of the tree; `2^DEPTH` is the maximum number of shielded notes the pool can hold.
+pub const DEPTH: u8 = 20;
+
+/// How many of the most recently produced roots a proof is allowed to reference, so a withdrawal
+/// built against a root that's since been superseded by a few deposits doesn't need to race a
+/// single current root.
+pub const RECENT_ROOTS_CAPACITY: u64 = 32;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MerkleTree {
+    /// Leaves in insertion order; `leaves.len()` is also the tree's current size.
+    leaves: Vector<[u8; 32]>,
+    /// Cached non-empty inner nodes, keyed by `(level, index within level)`. Level 0 holds leaf
+    /// hashes (mirroring `leaves`), level `DEPTH` holds the single root.
+    nodes: LookupMap<(u8, u64), [u8; 32]>,
+    /// Zero hash of an empty subtree at each level, precomputed once at construction.
+    zero_hashes: [[u8; 32]; (DEPTH as usize) + 1],
+    recent_roots: Vector<[u8; 32]>,
+    next_root_slot: u64,
+    current_root: [u8; 32],
+}
+
+impl MerkleTree {
+    pub fn new(leaves_prefix: Vec<u8>, nodes_prefix: Vec<u8>, roots_prefix: Vec<u8>) -> Self {
+        let mut zero_hashes = [[0u8; 32]; (DEPTH as usize) + 1];
+        for level in 1..=(DEPTH as usize) {
+            zero_hashes[level] = mimc::hash(zero_hashes[level - 1], zero_hashes[level - 1]);
+        }
+        let empty_root = zero_hashes[DEPTH as usize];
+        let mut tree = Self {
+            leaves: Vector::new(leaves_prefix),
+            nodes: LookupMap::new(nodes_prefix),
+            zero_hashes,
+            recent_roots: Vector::new(roots_prefix),
+            next_root_slot: 0,
+            current_root: empty_root,
+        };
+        tree.push_recent_root(empty_root);
+        tree
+    }
+
+    pub fn size(&self) -> u64 {
+        self.leaves.len()
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.current_root
+    }
+
+    pub fn is_known_root(&self, root: [u8; 32]) -> bool {
+        self.recent_roots.iter().any(|known| known == root)
+    }
+
+    /// Inserts `leaf` as the next commitment, recomputing the path to the root.
+    pub fn insert(&mut self, leaf: [u8; 32]) -> u64 {
+        let index = self.leaves.len();
+        assert!(index < (1u64 << DEPTH), "Shielded pool is full");
+        self.leaves.push(&leaf);
+        self.nodes.insert(&(0, index), &leaf);
+
+        let mut node_index = index;
+        let mut node_hash = leaf;
+        for level in 0..(DEPTH as usize) {
+            let (left, right) = if node_index % 2 == 0 {
+                let sibling = self.node_at(level as u8, node_index + 1);
+                (node_hash, sibling)
+            } else {
+                let sibling = self.node_at(level as u8, node_index - 1);
+                (sibling, node_hash)
+            };
+            node_hash = mimc::hash(left, right);
+            node_index /= 2;
+            self.nodes.insert(&((level + 1) as u8, node_index), &node_hash);
+        }
+        self.push_recent_root(node_hash);
+        index
+    }
+
+    fn node_at(&self, level: u8, index: u64) -> [u8; 32] {
+        self.nodes.get(&(level, index)).unwrap_or(self.zero_hashes[level as usize])
+    }
+
+    fn push_recent_root(&mut self, root: [u8; 32]) {
+        if self.recent_roots.len() < RECENT_ROOTS_CAPACITY {
+            self.recent_roots.push(&root);
+        } else {
+            self.recent_roots.replace(self.next_root_slot, &root);
+        }
+        self.next_root_slot = (self.next_root_slot + 1) % RECENT_ROOTS_CAPACITY;
+        self.current_root = root;
+    }
+}
@@ -0,0 +1,64 @@
+//! MiMC (`x -> (x + k + c_i)^3`) permutation used as the hash for both commitment leaves and
+//! Merkle inner nodes. The round constants are generated deterministically from a fixed seed
+//! (reduced mod the modulus) so no external constant table needs to be vendored into the contract.
+
+/// Field modulus the permutation operates over: a 127-bit prime, chosen so the sum of two reduced
+/// field elements always fits in a `u128`; products are reduced step by step in `mulmod`.
+pub const MODULUS: u128 = 170_141_183_460_469_231_731_687_303_715_884_105_727; // 2^127 - 1
+
+const ROUNDS: usize = 64;
+
+fn round_constants() -> [u128; ROUNDS] {
+    let mut constants = [0u128; ROUNDS];
+    let mut state: u128 = 0x6d694d43_436f6e7374616e7473; // arbitrary fixed seed ("miMCConstants")
+    for constant in constants.iter_mut() {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1) % MODULUS;
+        *constant = state;
+    }
+    constants
+}
+
+fn mulmod(a: u128, b: u128) -> u128 {
+    // 128x128 multiplication can overflow a u128, so accumulate via modular addition instead
+    // (binary "double-and-add" multiplication).
+    let mut result: u128 = 0;
+    let mut base = a % MODULUS;
+    let mut exp = b % MODULUS;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = (result + base) % MODULUS;
+        }
+        base = (base + base) % MODULUS;
+        exp >>= 1;
+    }
+    result
+}
+
+fn cubemod(x: u128) -> u128 {
+    mulmod(mulmod(x, x), x)
+}
+
+/// The full `ROUNDS`-round MiMC permutation over `(left, right)`, folding `right` into `left` the way a
+/// Miyaguchi-Preneel style compression does, so the function can double as a 2-to-1 hash.
+pub fn hash(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+    let constants = round_constants();
+    let mut x = bytes_to_field(left);
+    let k = bytes_to_field(right);
+    for c in constants.iter() {
+        x = cubemod(((x + k) % MODULUS + *c) % MODULUS);
+    }
+    x = (x + k) % MODULUS;
+    field_to_bytes(x)
+}
+
+fn bytes_to_field(bytes: [u8; 32]) -> u128 {
+    let mut limb = [0u8; 16];
+    limb.copy_from_slice(&bytes[16..32]);
+    u128::from_be_bytes(limb) % MODULUS
+}
+
+fn field_to_bytes(value: u128) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[16..32].copy_from_slice(&value.to_be_bytes());
+    out
+}
@@ -0,0 +1,161 @@
+//! Optional privacy layer over the plain NEP-141 balances: accounts `shield` tokens into an
+//! append-only commitment tree, then later `unshield` (exit to a clear account) or
+//! `private_transfer` (move value to a new hidden commitment) by presenting a Groth16 proof that
+//! they know a commitment opening under a known root, without revealing which one.
+
+mod groth16;
+mod merkle;
+mod mimc;
+
+use near_sdk::json_types::U128;
+use near_sdk::{env, log, near_bindgen, AccountId, Promise};
+
+use crate::Contract;
+pub use groth16::{Proof, VerifyingKey};
+pub use merkle::{DEPTH, RECENT_ROOTS_CAPACITY};
+
+pub(crate) type Hash = [u8; 32];
+pub(crate) type ShieldedTree = merkle::MerkleTree;
+pub(crate) type Nullifiers = near_sdk::collections::UnorderedSet<Hash>;
+
+pub(crate) fn new_tree() -> ShieldedTree {
+    ShieldedTree::new(b"sl".to_vec(), b"sn".to_vec(), b"sr".to_vec())
+}
+
+pub(crate) fn new_nullifiers() -> Nullifiers {
+    Nullifiers::new(b"su".to_vec())
+}
+
+fn account_to_field(account_id: &AccountId) -> Hash {
+    mimc::hash([0u8; 32], account_id_bytes(account_id))
+}
+
+fn account_id_bytes(account_id: &AccountId) -> Hash {
+    let bytes = account_id.as_bytes();
+    let mut padded = [0u8; 32];
+    let len = bytes.len().min(32);
+    padded[..len].copy_from_slice(&bytes[bytes.len() - len..]);
+    padded
+}
+
+fn amount_to_field(amount: u128) -> Hash {
+    let mut out = [0u8; 32];
+    out[16..32].copy_from_slice(&amount.to_be_bytes());
+    out
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Withdraws `amount` from the caller's transparent balance and inserts `commitment` (a
+    /// `MiMC(nullifier, secret, amount)` the caller computed off-chain) as the next leaf.
+    /// Returns the leaf's index in the tree.
+    ///
+    /// The attached deposit must cover the storage this adds to the Merkle tree (a new leaf plus
+    /// the recomputed path to the root), the same accounting `storage_deposit` uses elsewhere in
+    /// this contract; any excess is refunded.
+    #[payable]
+    pub fn shield(&mut self, amount: U128, commitment: Hash) -> u64 {
+        self.assert_not_paused();
+        assert!(amount.0 > 0, "The shielded amount should be a positive number");
+        let caller = env::predecessor_account_id();
+        self.assert_not_blacklisted(&caller);
+
+        let initial_storage_usage = env::storage_usage();
+        self.token.internal_withdraw(&caller, amount.0);
+        let index = self.shielded_tree.insert(commitment);
+        let storage_cost =
+            (env::storage_usage() - initial_storage_usage) as u128 * env::storage_byte_cost();
+        let attached_deposit = env::attached_deposit();
+        assert!(
+            attached_deposit >= storage_cost,
+            "Must attach at least {} yoctoNEAR to cover the storage added by shield",
+            storage_cost
+        );
+        if attached_deposit > storage_cost {
+            Promise::new(caller.clone()).transfer(attached_deposit - storage_cost);
+        }
+
+        log!("Shielded {} from @{} into leaf {}", amount.0, caller, index);
+        index
+    }
+
+    /// Withdraws `amount` out of the shielded pool to `recipient`'s transparent balance, proving
+    /// knowledge of a spendable note under `root` without revealing which leaf it is.
+    pub fn unshield(
+        &mut self,
+        proof: Proof,
+        root: Hash,
+        nullifier_hash: Hash,
+        recipient: AccountId,
+        amount: U128,
+    ) {
+        self.assert_not_paused();
+        self.assert_not_blacklisted(&recipient);
+        let public_inputs =
+            [root, nullifier_hash, account_to_field(&recipient), amount_to_field(amount.0)];
+        self.spend_note(&proof, root, nullifier_hash, &public_inputs);
+        self.token.internal_deposit(&recipient, amount.0);
+        log!("Unshielded {} to @{}", amount.0, recipient);
+    }
+
+    /// Moves `amount` from one shielded note to a new one (`new_commitment`) without ever
+    /// touching a transparent balance, proving knowledge of a spendable note under `root`.
+    pub fn private_transfer(
+        &mut self,
+        proof: Proof,
+        root: Hash,
+        nullifier_hash: Hash,
+        new_commitment: Hash,
+        amount: U128,
+    ) -> u64 {
+        self.assert_not_paused();
+        let public_inputs = [root, nullifier_hash, new_commitment, amount_to_field(amount.0)];
+        self.spend_note(&proof, root, nullifier_hash, &public_inputs);
+        let index = self.shielded_tree.insert(new_commitment);
+        log!("Private-transferred {} into leaf {}", amount.0, index);
+        index
+    }
+
+    /// Sets the verifying key `unshield`/`private_transfer` check proofs against. Restricted to
+    /// the contract owner. Until this is called, both methods panic: there is no verifying key
+    /// compiled into this contract (see `groth16`'s module docs), so there's nothing valid to
+    /// check a proof against yet.
+    pub fn set_shielded_verifying_key(&mut self, verifying_key: VerifyingKey) {
+        self.assert_owner();
+        verifying_key.assert_well_formed();
+        self.shielded_vk = Some(verifying_key);
+    }
+
+    /// Whether a verifying key has been configured, i.e. whether `unshield`/`private_transfer`
+    /// are currently reachable.
+    pub fn is_shielded_verifying_key_set(&self) -> bool {
+        self.shielded_vk.is_some()
+    }
+
+    /// Current Merkle root of the shielded commitment tree.
+    pub fn shielded_root(&self) -> Hash {
+        self.shielded_tree.root()
+    }
+
+    /// Number of commitments inserted into the shielded pool so far.
+    pub fn shielded_size(&self) -> u64 {
+        self.shielded_tree.size()
+    }
+
+    fn spend_note(
+        &mut self,
+        proof: &Proof,
+        root: Hash,
+        nullifier_hash: Hash,
+        public_inputs: &[Hash; groth16::PUBLIC_INPUTS],
+    ) {
+        let vk = self
+            .shielded_vk
+            .as_ref()
+            .expect("Shielded pool verifying key is not configured yet; spends are disabled");
+        assert!(self.shielded_tree.is_known_root(root), "Unknown or stale merkle root");
+        assert!(!self.nullifiers.contains(&nullifier_hash), "Note has already been spent");
+        assert!(groth16::verify(vk, proof, public_inputs), "Invalid shielded pool proof");
+        self.nullifiers.insert(&nullifier_hash);
+    }
+}